@@ -0,0 +1,151 @@
+use crate::types::*;
+use rustc_hash::FxHashMap;
+use wasm_bindgen::prelude::*;
+
+const GAIN_EPSILON: f64 = 1e-9;
+
+/// One Louvain optimization pass: repeatedly move each node into whichever
+/// neighboring community maximizes the modularity gain
+/// `dQ = k_i_in / (2m) - sigma_tot * k_i / (2m)^2`, until a full sweep makes
+/// no moves. Weights are treated as undirected, so each link's weight counts
+/// toward both endpoints' weighted degree.
+///
+/// Returns each node's final community label: the index of one of the nodes
+/// in that community, so labels are not contiguous (`detect_communities`
+/// renumbers them compactly). Isolated nodes keep their own singleton
+/// community, since they have no neighbor to move toward.
+fn louvain_pass(n: usize, adjacency: &[Vec<(usize, f32)>], k: &[f64], m2: f64) -> Vec<usize> {
+    let mut communities: Vec<usize> = (0..n).collect();
+    let mut sigma_tot: Vec<f64> = k.to_vec();
+
+    if m2 <= 0.0 {
+        return communities;
+    }
+
+    loop {
+        let mut moved = false;
+
+        for i in 0..n {
+            let current_comm = communities[i];
+            sigma_tot[current_comm] -= k[i];
+
+            let mut neighbor_weights: FxHashMap<usize, f64> = FxHashMap::default();
+            for &(j, w) in &adjacency[i] {
+                if j == i {
+                    continue;
+                }
+                *neighbor_weights.entry(communities[j]).or_insert(0.0) += w as f64;
+            }
+
+            let mut best_comm = current_comm;
+            let mut best_gain = neighbor_weights.get(&current_comm).copied().unwrap_or(0.0)
+                - sigma_tot[current_comm] * k[i] / m2;
+
+            for (&comm, &k_i_in) in &neighbor_weights {
+                let gain = k_i_in - sigma_tot[comm] * k[i] / m2;
+                if gain > best_gain + GAIN_EPSILON {
+                    best_gain = gain;
+                    best_comm = comm;
+                }
+            }
+
+            sigma_tot[best_comm] += k[i];
+            if best_comm != current_comm {
+                communities[i] = best_comm;
+                moved = true;
+            }
+        }
+
+        if !moved {
+            break;
+        }
+    }
+
+    communities
+}
+
+/// Detect artist communities via one Louvain modularity-optimization pass
+/// over the weighted similarity graph, so the front-end can color nodes by
+/// cluster instead of by `isCenter` alone.
+///
+/// Performance: O(iterations * (N + E)) until the sweep converges.
+#[wasm_bindgen]
+pub fn detect_communities(graph_json: &JsValue) -> Result<JsValue, JsValue> {
+    let mut graph: ResolvedGraph = serde_wasm_bindgen::from_value(graph_json.clone())
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse graph: {}", e)))?;
+
+    let n = graph.nodes.len();
+    let mut adjacency: Vec<Vec<(usize, f32)>> = vec![Vec::new(); n];
+    let mut k = vec![0.0f64; n];
+    let mut m = 0.0f64;
+
+    for link in &graph.links {
+        let (s, t) = (link.source as usize, link.target as usize);
+        adjacency[s].push((t, link.weight));
+        adjacency[t].push((s, link.weight));
+        k[s] += link.weight as f64;
+        k[t] += link.weight as f64;
+        m += link.weight as f64;
+    }
+
+    let communities = louvain_pass(n, &adjacency, &k, 2.0 * m);
+
+    let mut remap: FxHashMap<usize, u32> = FxHashMap::default();
+    for (i, node) in graph.nodes.iter_mut().enumerate() {
+        let raw = communities[i];
+        let next_id = remap.len() as u32;
+        let id = *remap.entry(raw).or_insert(next_id);
+        node.community = Some(id);
+    }
+
+    serde_wasm_bindgen::to_value(&graph)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolated_nodes_keep_singleton_community() {
+        let adjacency: Vec<Vec<(usize, f32)>> = vec![Vec::new(), Vec::new()];
+        let k = vec![0.0, 0.0];
+        let communities = louvain_pass(2, &adjacency, &k, 0.0);
+        assert_eq!(communities, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_two_tight_pairs_end_up_in_separate_communities() {
+        // 0-1 and 2-3 are each strongly connected, with a much weaker 1-2 bridge.
+        let adjacency = vec![
+            vec![(1, 10.0)],
+            vec![(0, 10.0), (2, 0.1)],
+            vec![(1, 0.1), (3, 10.0)],
+            vec![(2, 10.0)],
+        ];
+        let k = vec![10.0, 10.1, 10.1, 10.0];
+        let m2 = 2.0 * (10.0 + 0.1 + 10.0);
+
+        let communities = louvain_pass(4, &adjacency, &k, m2);
+
+        assert_eq!(communities[0], communities[1]);
+        assert_eq!(communities[2], communities[3]);
+        assert_ne!(communities[0], communities[2]);
+    }
+
+    #[test]
+    fn test_fully_connected_triangle_shares_one_community() {
+        let adjacency = vec![
+            vec![(1, 1.0), (2, 1.0)],
+            vec![(0, 1.0), (2, 1.0)],
+            vec![(0, 1.0), (1, 1.0)],
+        ];
+        let k = vec![2.0, 2.0, 2.0];
+        let m2 = 2.0 * 3.0;
+
+        let communities = louvain_pass(3, &adjacency, &k, m2);
+
+        assert_eq!(communities[0], communities[1]);
+        assert_eq!(communities[1], communities[2]);
+    }
+}
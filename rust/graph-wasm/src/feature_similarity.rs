@@ -0,0 +1,182 @@
+use crate::graph_processor::normalize;
+use crate::types::*;
+use rustc_hash::FxHashSet;
+use wasm_bindgen::prelude::*;
+
+/// Per-dimension z-score normalization across all feature vectors.
+///
+/// A dimension with zero variance (every artist shares the same value)
+/// is left at zero after normalization rather than dividing by zero.
+fn z_score_normalize(vectors: &[Vec<f32>]) -> Vec<Vec<f32>> {
+    let dims = vectors[0].len();
+    let n = vectors.len() as f32;
+
+    let mut means = vec![0.0f32; dims];
+    for v in vectors {
+        for (d, &value) in v.iter().enumerate() {
+            means[d] += value;
+        }
+    }
+    for m in &mut means {
+        *m /= n;
+    }
+
+    let mut std_devs = vec![0.0f32; dims];
+    for v in vectors {
+        for (d, &value) in v.iter().enumerate() {
+            let diff = value - means[d];
+            std_devs[d] += diff * diff;
+        }
+    }
+    for s in &mut std_devs {
+        *s = (*s / n).sqrt();
+    }
+
+    vectors
+        .iter()
+        .map(|v| {
+            v.iter()
+                .enumerate()
+                .map(|(d, &value)| {
+                    if std_devs[d] > f32::EPSILON {
+                        (value - means[d]) / std_devs[d]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[inline]
+fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// Build similarity edges directly from per-artist feature vectors, bliss-style.
+///
+/// For each artist, computes the Euclidean distance (after per-dimension
+/// z-score normalization) to every other artist, converts it to a similarity
+/// weight `w = 1 / (1 + d)`, and keeps each node's top-`k` neighbors whose
+/// weight exceeds `threshold`. The resulting kNN graph is symmetrized and
+/// deduplicated, so the output can feed the same `Edge` pipeline that
+/// `process_graph_data` consumes from Last.fm.
+///
+/// Performance: O(N^2 * D) for the pairwise distance matrix.
+#[wasm_bindgen]
+pub fn edges_from_features(
+    features_json: &JsValue,
+    k: u32,
+    threshold: f32,
+) -> Result<JsValue, JsValue> {
+    let features: Vec<FeatureVector> = serde_wasm_bindgen::from_value(features_json.clone())
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse features: {}", e)))?;
+
+    if features.len() < 2 {
+        return serde_wasm_bindgen::to_value(&Vec::<Edge>::new())
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)));
+    }
+
+    let dims = features[0].features.len();
+    if dims == 0 {
+        return Err(JsValue::from_str("Feature vectors must not be empty"));
+    }
+    for fv in &features {
+        if fv.features.len() != dims {
+            return Err(JsValue::from_str(&format!(
+                "Feature vector for '{}' has {} dimensions, expected {}",
+                fv.name,
+                fv.features.len(),
+                dims
+            )));
+        }
+    }
+
+    let raw_vectors: Vec<Vec<f32>> = features.iter().map(|fv| fv.features.clone()).collect();
+    let normalized = z_score_normalize(&raw_vectors);
+
+    let n = features.len();
+    let k = k as usize;
+
+    // candidates[i] = top-k (neighbor_idx, weight) pairs for node i, sorted descending.
+    let mut candidates: Vec<Vec<(usize, f32)>> = Vec::with_capacity(n);
+    for i in 0..n {
+        let mut neighbors: Vec<(usize, f32)> = Vec::with_capacity(n - 1);
+        for j in 0..n {
+            if i == j {
+                continue;
+            }
+            let d = euclidean_distance(&normalized[i], &normalized[j]);
+            let weight = 1.0 / (1.0 + d);
+            if weight > threshold {
+                neighbors.push((j, weight));
+            }
+        }
+        neighbors.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        neighbors.truncate(k);
+        candidates.push(neighbors);
+    }
+
+    let mut seen_pairs: FxHashSet<(usize, usize)> = FxHashSet::default();
+    let mut edges: Vec<Edge> = Vec::new();
+
+    for (i, neighbors) in candidates.iter().enumerate() {
+        for &(j, weight) in neighbors {
+            let (lo, hi) = if normalize(&features[i].name) <= normalize(&features[j].name) {
+                (i, j)
+            } else {
+                (j, i)
+            };
+            if seen_pairs.insert((lo, hi)) {
+                edges.push(Edge {
+                    source: features[lo].name.clone(),
+                    target: features[hi].name.clone(),
+                    weight,
+                });
+            }
+        }
+    }
+
+    serde_wasm_bindgen::to_value(&edges)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_z_score_normalize_mean_zero() {
+        let vectors = vec![vec![1.0], vec![2.0], vec![3.0]];
+        let normalized = z_score_normalize(&vectors);
+        let mean: f32 = normalized.iter().map(|v| v[0]).sum::<f32>() / 3.0;
+        assert!(mean.abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_z_score_normalize_zero_variance_dimension() {
+        let vectors = vec![vec![5.0, 1.0], vec![5.0, 2.0], vec![5.0, 3.0]];
+        let normalized = z_score_normalize(&vectors);
+        for v in &normalized {
+            assert_eq!(v[0], 0.0);
+        }
+    }
+
+    #[test]
+    fn test_euclidean_distance() {
+        let a = vec![0.0, 0.0];
+        let b = vec![3.0, 4.0];
+        assert!((euclidean_distance(&a, &b) - 5.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_euclidean_distance_identical_vectors() {
+        let a = vec![1.0, 2.0, 3.0];
+        assert_eq!(euclidean_distance(&a, &a), 0.0);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::types::*;
+use wasm_bindgen::prelude::*;
+
+const THETA: f64 = 0.8;
+const REPULSION_STRENGTH: f64 = 30.0;
+const SPRING_LENGTH: f64 = 30.0;
+const SPRING_STRENGTH: f64 = 0.1;
+const GRAVITY_STRENGTH: f64 = 0.02;
+const VELOCITY_DECAY: f64 = 0.9;
+const ALPHA_MIN: f64 = 0.001;
+/// Below this cell size, stop subdividing and merge bodies into a single
+/// leaf instead. Without this, two coincident (or effectively coincident)
+/// points always land in the same quadrant, so `insert` would recurse
+/// forever as `bounds.size` halves toward zero without ever separating them.
+const MIN_CELL_SIZE: f64 = 1e-6;
+
+#[derive(Clone)]
+struct Bounds {
+    x_min: f64,
+    y_min: f64,
+    size: f64,
+}
+
+impl Bounds {
+    fn center(&self) -> (f64, f64) {
+        (self.x_min + self.size / 2.0, self.y_min + self.size / 2.0)
+    }
+
+    fn quadrant(&self, x: f64, y: f64) -> usize {
+        let (cx, cy) = self.center();
+        match (x >= cx, y >= cy) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (false, true) => 2,
+            (true, true) => 3,
+        }
+    }
+
+    fn child_bounds(&self, quadrant: usize) -> Bounds {
+        let half = self.size / 2.0;
+        match quadrant {
+            0 => Bounds { x_min: self.x_min, y_min: self.y_min, size: half },
+            1 => Bounds { x_min: self.x_min + half, y_min: self.y_min, size: half },
+            2 => Bounds { x_min: self.x_min, y_min: self.y_min + half, size: half },
+            _ => Bounds { x_min: self.x_min + half, y_min: self.y_min + half, size: half },
+        }
+    }
+}
+
+/// Barnes-Hut quadtree over current node positions, used to approximate the
+/// O(N^2) repulsive charge step in O(N log N).
+enum QuadTree {
+    Empty { bounds: Bounds },
+    Leaf { bounds: Bounds, x: f64, y: f64, mass: f64 },
+    Internal { bounds: Bounds, cx: f64, cy: f64, mass: f64, children: Vec<QuadTree> },
+}
+
+impl QuadTree {
+    fn insert(self, x: f64, y: f64, mass: f64) -> QuadTree {
+        match self {
+            QuadTree::Empty { bounds } => QuadTree::Leaf { bounds, x, y, mass },
+            QuadTree::Leaf { bounds, x: lx, y: ly, mass: lm } => {
+                let coincident = (x - lx).abs() < f64::EPSILON && (y - ly).abs() < f64::EPSILON;
+                if coincident || bounds.size < MIN_CELL_SIZE {
+                    let total = lm + mass;
+                    return QuadTree::Leaf {
+                        bounds,
+                        x: (lx * lm + x * mass) / total,
+                        y: (ly * lm + y * mass) / total,
+                        mass: total,
+                    };
+                }
+
+                let mut children: Vec<QuadTree> = (0..4)
+                    .map(|q| QuadTree::Empty { bounds: bounds.child_bounds(q) })
+                    .collect();
+                let lq = bounds.quadrant(lx, ly);
+                children[lq] = std::mem::replace(&mut children[lq], QuadTree::Empty {
+                    bounds: bounds.child_bounds(lq),
+                })
+                .insert(lx, ly, lm);
+                let nq = bounds.quadrant(x, y);
+                children[nq] = std::mem::replace(&mut children[nq], QuadTree::Empty {
+                    bounds: bounds.child_bounds(nq),
+                })
+                .insert(x, y, mass);
+                let total = lm + mass;
+                QuadTree::Internal {
+                    bounds,
+                    cx: (lx * lm + x * mass) / total,
+                    cy: (ly * lm + y * mass) / total,
+                    mass: total,
+                    children,
+                }
+            }
+            QuadTree::Internal { bounds, cx, cy, mass: existing_mass, mut children } => {
+                let q = bounds.quadrant(x, y);
+                children[q] = std::mem::replace(&mut children[q], QuadTree::Empty {
+                    bounds: bounds.child_bounds(q),
+                })
+                .insert(x, y, mass);
+                let new_mass = existing_mass + mass;
+                QuadTree::Internal {
+                    cx: (cx * existing_mass + x * mass) / new_mass,
+                    cy: (cy * existing_mass + y * mass) / new_mass,
+                    mass: new_mass,
+                    bounds,
+                    children,
+                }
+            }
+        }
+    }
+
+    /// Accumulate the repulsive force this (sub)tree exerts on a body at
+    /// `(x, y)`, recursing into child quadrants only when the cell is too
+    /// close relative to its width for the point-mass approximation to hold.
+    fn accumulate_repulsion(&self, x: f64, y: f64, fx: &mut f64, fy: &mut f64) {
+        match self {
+            QuadTree::Empty { .. } => {}
+            QuadTree::Leaf { x: lx, y: ly, mass, .. } => {
+                apply_point_repulsion(x, y, *lx, *ly, *mass, fx, fy);
+            }
+            QuadTree::Internal { bounds, cx, cy, mass, children } => {
+                let dx = x - cx;
+                let dy = y - cy;
+                let dist = (dx * dx + dy * dy).sqrt();
+                if dist > 0.0 && bounds.size / dist < THETA {
+                    apply_point_repulsion(x, y, *cx, *cy, *mass, fx, fy);
+                } else {
+                    for child in children {
+                        child.accumulate_repulsion(x, y, fx, fy);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+fn apply_point_repulsion(x: f64, y: f64, ox: f64, oy: f64, mass: f64, fx: &mut f64, fy: &mut f64) {
+    let dx = x - ox;
+    let dy = y - oy;
+    let dist_sq = dx * dx + dy * dy;
+    if dist_sq < 1e-9 {
+        return; // Coincident points (e.g. a body against its own leaf).
+    }
+    let dist = dist_sq.sqrt();
+    let force = REPULSION_STRENGTH * mass / dist_sq;
+    *fx += force * dx / dist;
+    *fy += force * dy / dist;
+}
+
+fn build_quadtree(positions: &[(f64, f64)]) -> QuadTree {
+    let (mut x_min, mut y_min, mut x_max, mut y_max) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for &(x, y) in positions {
+        x_min = x_min.min(x);
+        y_min = y_min.min(y);
+        x_max = x_max.max(x);
+        y_max = y_max.max(y);
+    }
+    let size = (x_max - x_min).max(y_max - y_min).max(1.0) * 1.1;
+    let bounds = Bounds { x_min: x_min - size * 0.05, y_min: y_min - size * 0.05, size };
+
+    let mut tree = QuadTree::Empty { bounds };
+    for &(x, y) in positions {
+        tree = tree.insert(x, y, 1.0);
+    }
+    tree
+}
+
+/// Run a force-directed simulation in Rust so large graphs don't bottleneck
+/// on the JS force loop, using a Barnes-Hut quadtree for O(N log N) repulsive
+/// charge, Hooke's-law spring attraction along each `ResolvedLink` scaled by
+/// its weight, and a gentle gravity toward center.
+///
+/// Velocity decays by `VELOCITY_DECAY` each iteration and the simulation
+/// cools via a decreasing alpha, mirroring d3-force's own schedule. Pinned
+/// nodes (`fx`/`fy` both set) are excluded from position updates.
+///
+/// Performance: O(iterations * N log N).
+#[wasm_bindgen]
+pub fn compute_layout(
+    graph_json: &JsValue,
+    iterations: u32,
+    width: f64,
+    height: f64,
+) -> Result<JsValue, JsValue> {
+    let mut graph: ResolvedGraph = serde_wasm_bindgen::from_value(graph_json.clone())
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse graph: {}", e)))?;
+
+    let n = graph.nodes.len();
+    if n == 0 {
+        return serde_wasm_bindgen::to_value(&graph)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)));
+    }
+
+    let mut positions: Vec<(f64, f64)> = (0..n)
+        .map(|i| {
+            let node = &graph.nodes[i];
+            match (node.x, node.y) {
+                (Some(x), Some(y)) => (x, y),
+                _ => {
+                    let angle = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                    let radius = width.min(height) / 4.0;
+                    (width / 2.0 + radius * angle.cos(), height / 2.0 + radius * angle.sin())
+                }
+            }
+        })
+        .collect();
+    let mut velocities: Vec<(f64, f64)> = vec![(0.0, 0.0); n];
+    let pinned: Vec<Option<(f64, f64)>> = graph
+        .nodes
+        .iter()
+        .map(|node| match (node.fx, node.fy) {
+            (Some(fx), Some(fy)) => Some((fx, fy)),
+            _ => None,
+        })
+        .collect();
+
+    let alpha_decay = 1.0 - ALPHA_MIN.powf(1.0 / iterations.max(1) as f64);
+    let mut alpha = 1.0;
+    let center = (width / 2.0, height / 2.0);
+
+    for _ in 0..iterations {
+        let quadtree = build_quadtree(&positions);
+        let mut forces = vec![(0.0, 0.0); n];
+
+        for i in 0..n {
+            let (x, y) = positions[i];
+            let (mut fx, mut fy) = (0.0, 0.0);
+            quadtree.accumulate_repulsion(x, y, &mut fx, &mut fy);
+
+            fx += (center.0 - x) * GRAVITY_STRENGTH;
+            fy += (center.1 - y) * GRAVITY_STRENGTH;
+
+            forces[i] = (fx, fy);
+        }
+
+        for link in &graph.links {
+            let (sx, sy) = positions[link.source as usize];
+            let (tx, ty) = positions[link.target as usize];
+            let dx = tx - sx;
+            let dy = ty - sy;
+            let dist = (dx * dx + dy * dy).sqrt().max(1e-6);
+            let displacement = dist - SPRING_LENGTH;
+            let force = SPRING_STRENGTH * link.weight as f64 * displacement;
+            let (ux, uy) = (dx / dist, dy / dist);
+
+            forces[link.source as usize].0 += force * ux;
+            forces[link.source as usize].1 += force * uy;
+            forces[link.target as usize].0 -= force * ux;
+            forces[link.target as usize].1 -= force * uy;
+        }
+
+        for i in 0..n {
+            if let Some((fx, fy)) = pinned[i] {
+                positions[i] = (fx, fy);
+                velocities[i] = (0.0, 0.0);
+                continue;
+            }
+
+            let (fx, fy) = forces[i];
+            let (vx, vy) = velocities[i];
+            let new_vx = (vx + fx * alpha) * VELOCITY_DECAY;
+            let new_vy = (vy + fy * alpha) * VELOCITY_DECAY;
+            velocities[i] = (new_vx, new_vy);
+
+            let (x, y) = positions[i];
+            positions[i] = (x + new_vx, y + new_vy);
+        }
+
+        alpha *= 1.0 - alpha_decay;
+    }
+
+    for (i, node) in graph.nodes.iter_mut().enumerate() {
+        let (x, y) = positions[i];
+        node.x = Some(x);
+        node.y = Some(y);
+    }
+
+    serde_wasm_bindgen::to_value(&graph)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadrant_splits_bounds_into_four() {
+        let bounds = Bounds { x_min: 0.0, y_min: 0.0, size: 10.0 };
+        assert_eq!(bounds.quadrant(2.0, 2.0), 0);
+        assert_eq!(bounds.quadrant(8.0, 2.0), 1);
+        assert_eq!(bounds.quadrant(2.0, 8.0), 2);
+        assert_eq!(bounds.quadrant(8.0, 8.0), 3);
+    }
+
+    #[test]
+    fn test_insert_coincident_points_merges_instead_of_recursing_forever() {
+        let bounds = Bounds { x_min: 0.0, y_min: 0.0, size: 10.0 };
+        let tree = QuadTree::Empty { bounds }.insert(5.0, 5.0, 1.0).insert(5.0, 5.0, 1.0);
+
+        match tree {
+            QuadTree::Leaf { x, y, mass, .. } => {
+                assert!((x - 5.0).abs() < 1e-9);
+                assert!((y - 5.0).abs() < 1e-9);
+                assert!((mass - 2.0).abs() < 1e-9);
+            }
+            _ => panic!("expected coincident points to merge into a single leaf"),
+        }
+    }
+
+    #[test]
+    fn test_build_quadtree_many_coincident_points_terminates() {
+        // Regression test: previously recursed until `bounds.size` underflowed
+        // to zero (e.g. all nodes seeded at the origin, or several pinned at
+        // the same fx/fy) instead of terminating.
+        let positions = vec![(0.0, 0.0); 16];
+        let tree = build_quadtree(&positions);
+
+        let (mut fx, mut fy) = (0.0, 0.0);
+        tree.accumulate_repulsion(0.0, 0.0, &mut fx, &mut fy);
+        assert_eq!(fx, 0.0);
+        assert_eq!(fy, 0.0);
+    }
+
+    #[test]
+    fn test_insert_computes_center_of_mass() {
+        let bounds = Bounds { x_min: 0.0, y_min: 0.0, size: 10.0 };
+        let tree = QuadTree::Empty { bounds }.insert(2.0, 2.0, 1.0).insert(8.0, 2.0, 1.0);
+
+        match tree {
+            QuadTree::Internal { cx, cy, mass, .. } => {
+                assert!((cx - 5.0).abs() < 1e-9);
+                assert!((cy - 2.0).abs() < 1e-9);
+                assert!((mass - 2.0).abs() < 1e-9);
+            }
+            _ => panic!("expected an internal node after two inserts"),
+        }
+    }
+
+    #[test]
+    fn test_point_repulsion_pushes_away_from_source() {
+        let (mut fx, mut fy) = (0.0, 0.0);
+        apply_point_repulsion(5.0, 0.0, 0.0, 0.0, 1.0, &mut fx, &mut fy);
+        assert!(fx > 0.0);
+        assert!((fy).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_point_repulsion_skips_coincident_points() {
+        let (mut fx, mut fy) = (0.0, 0.0);
+        apply_point_repulsion(3.0, 3.0, 3.0, 3.0, 1.0, &mut fx, &mut fy);
+        assert_eq!(fx, 0.0);
+        assert_eq!(fy, 0.0);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::graph_processor::normalize;
+use crate::types::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+use wasm_bindgen::prelude::*;
+
+/// Dedup key for a node: prefer the stable Last.fm MBID when present, since
+/// two fetches can disagree on spelling/casing for the same artist; fall
+/// back to the normalized name otherwise.
+fn dedup_key(node: &GraphNode) -> String {
+    match &node.mbid {
+        Some(mbid) => format!("mbid:{}", mbid),
+        None => format!("name:{}", normalize(&node.name)),
+    }
+}
+
+fn max_option(a: Option<u32>, b: Option<u32>) -> Option<u32> {
+    match (a, b) {
+        (Some(x), Some(y)) => Some(x.max(y)),
+        (Some(x), None) => Some(x),
+        (None, Some(y)) => Some(y),
+        (None, None) => None,
+    }
+}
+
+fn union_tags(existing: Option<Vec<String>>, incoming: Option<Vec<String>>) -> Option<Vec<String>> {
+    match (existing, incoming) {
+        (None, None) => None,
+        (Some(tags), None) => Some(tags),
+        (None, Some(tags)) => Some(tags),
+        (Some(mut tags), Some(more)) => {
+            let mut seen: FxHashSet<String> = tags.iter().cloned().collect();
+            for tag in more {
+                if seen.insert(tag.clone()) {
+                    tags.push(tag);
+                }
+            }
+            Some(tags)
+        }
+    }
+}
+
+/// Merge another fetch's node into a surviving node: prefer the first
+/// non-`None` `url`/`image_url`, take the max of `listeners`/`playcount`,
+/// union `tags`, and keep `is_center` if either copy was the center.
+fn merge_into(existing: &mut GraphNode, incoming: GraphNode) {
+    if existing.url.is_none() {
+        existing.url = incoming.url;
+    }
+    if existing.image_url.is_none() {
+        existing.image_url = incoming.image_url;
+    }
+    existing.listeners = max_option(existing.listeners, incoming.listeners);
+    existing.playcount = max_option(existing.playcount, incoming.playcount);
+    existing.tags = union_tags(existing.tags.take(), incoming.tags);
+    existing.is_center = existing.is_center || incoming.is_center;
+}
+
+/// Combine several fetch results into one graph, dedup'd by MBID (falling
+/// back to normalized name) rather than `process_graph_data`'s name-only
+/// dedup, which collides for same-name artists and splits spelling variants
+/// of the same MBID across fetches.
+///
+/// After node merging, every link's source/target is rewritten to the
+/// surviving canonical name; self-loops created by the merge are dropped,
+/// and duplicate links between the same pair collapse to the maximum weight.
+///
+/// Performance: O(N + E) per input graph.
+fn merge_all(graphs: Vec<ProcessedGraph>) -> ProcessedGraph {
+    let mut merged_nodes: Vec<GraphNode> = Vec::new();
+    let mut key_to_idx: FxHashMap<String, usize> = FxHashMap::default();
+
+    let mut merged_links: Vec<GraphLink> = Vec::new();
+    // Keyed by the survivors' node indices, not their names: two distinct
+    // surviving nodes can share a normalized name (same-name-different-MBID
+    // artists), and a name-keyed map would wrongly collapse their links.
+    let mut link_idx: FxHashMap<(usize, usize), usize> = FxHashMap::default();
+
+    for graph in graphs {
+        // Maps this graph's own normalized node names to the index of the
+        // surviving node they were merged into, so this graph's links can
+        // be rewritten below.
+        let mut rename_map: FxHashMap<String, usize> = FxHashMap::default();
+
+        for node in graph.nodes {
+            let key = dedup_key(&node);
+            let normalized_name = normalize(&node.name);
+
+            if let Some(&idx) = key_to_idx.get(&key) {
+                merge_into(&mut merged_nodes[idx], node);
+                rename_map.insert(normalized_name, idx);
+            } else {
+                let idx = merged_nodes.len();
+                key_to_idx.insert(key, idx);
+                rename_map.insert(normalized_name, idx);
+                merged_nodes.push(node);
+            }
+        }
+
+        for link in graph.links {
+            let source_idx = rename_map.get(&normalize(&link.source));
+            let target_idx = rename_map.get(&normalize(&link.target));
+
+            let (source_idx, target_idx) = match (source_idx, target_idx) {
+                (Some(&s), Some(&t)) => (s, t),
+                _ => continue,
+            };
+
+            if source_idx == target_idx {
+                continue; // Dedup-induced self-loop.
+            }
+
+            let (lo, hi) = if normalize(&merged_nodes[source_idx].name)
+                <= normalize(&merged_nodes[target_idx].name)
+            {
+                (source_idx, target_idx)
+            } else {
+                (target_idx, source_idx)
+            };
+            let pair_key = (lo, hi);
+
+            match link_idx.get(&pair_key) {
+                Some(&idx) => {
+                    if link.weight > merged_links[idx].weight {
+                        merged_links[idx].weight = link.weight;
+                    }
+                }
+                None => {
+                    link_idx.insert(pair_key, merged_links.len());
+                    merged_links.push(GraphLink {
+                        source: merged_nodes[lo].name.clone(),
+                        target: merged_nodes[hi].name.clone(),
+                        weight: link.weight,
+                    });
+                }
+            }
+        }
+    }
+
+    ProcessedGraph {
+        nodes: merged_nodes,
+        links: merged_links,
+    }
+}
+
+/// `wasm_bindgen` entry point: parse the incoming graphs and delegate to
+/// [`merge_all`].
+#[wasm_bindgen]
+pub fn merge_graphs(graphs_json: &JsValue) -> Result<JsValue, JsValue> {
+    let graphs: Vec<ProcessedGraph> = serde_wasm_bindgen::from_value(graphs_json.clone())
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse graphs: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&merge_all(graphs))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_node(name: &str, mbid: Option<&str>) -> GraphNode {
+        GraphNode {
+            id: None,
+            name: name.to_string(),
+            mbid: mbid.map(|m| m.to_string()),
+            url: None,
+            image_url: None,
+            listeners: None,
+            playcount: None,
+            tags: None,
+            lastfm_url: None,
+            is_center: false,
+            x: None,
+            y: None,
+            fx: None,
+            fy: None,
+            community: None,
+        }
+    }
+
+    #[test]
+    fn test_dedup_key_prefers_mbid() {
+        let a = make_node("Radiohead", Some("mbid-1"));
+        let b = make_node("radiohead ", Some("mbid-1"));
+        assert_eq!(dedup_key(&a), dedup_key(&b));
+    }
+
+    #[test]
+    fn test_dedup_key_falls_back_to_normalized_name() {
+        let a = make_node("The Beatles", None);
+        let b = make_node("THE BEATLES", None);
+        assert_eq!(dedup_key(&a), dedup_key(&b));
+    }
+
+    #[test]
+    fn test_max_option() {
+        assert_eq!(max_option(Some(3), Some(7)), Some(7));
+        assert_eq!(max_option(Some(3), None), Some(3));
+        assert_eq!(max_option(None, None), None);
+    }
+
+    #[test]
+    fn test_union_tags_dedup_preserves_order() {
+        let existing = Some(vec!["rock".to_string(), "british".to_string()]);
+        let incoming = Some(vec!["british".to_string(), "psychedelic".to_string()]);
+        let merged = union_tags(existing, incoming).unwrap();
+        assert_eq!(merged, vec!["rock", "british", "psychedelic"]);
+    }
+
+    #[test]
+    fn test_merge_into_prefers_first_non_none_url() {
+        let mut existing = make_node("Radiohead", None);
+        existing.url = Some("http://first.example".to_string());
+        let mut incoming = make_node("Radiohead", None);
+        incoming.url = Some("http://second.example".to_string());
+
+        merge_into(&mut existing, incoming);
+
+        assert_eq!(existing.url, Some("http://first.example".to_string()));
+    }
+
+    #[test]
+    fn test_merge_into_keeps_center_if_either_was_center() {
+        let mut existing = make_node("Radiohead", None);
+        let mut incoming = make_node("Radiohead", None);
+        incoming.is_center = true;
+
+        merge_into(&mut existing, incoming);
+
+        assert!(existing.is_center);
+    }
+
+    #[test]
+    fn test_merge_graphs_keeps_links_separate_for_same_name_different_mbid() {
+        // Two distinct "Suede" artists (different MBIDs) each link to "Z".
+        // Both links must survive with their own weights, not collapse
+        // because the link-dedup key used to be the normalized node name.
+        let graph_a = ProcessedGraph {
+            nodes: vec![make_node("Suede", Some("mbid-a")), make_node("Z", Some("mbid-z"))],
+            links: vec![GraphLink {
+                source: "Suede".to_string(),
+                target: "Z".to_string(),
+                weight: 0.1,
+            }],
+        };
+        let graph_b = ProcessedGraph {
+            nodes: vec![make_node("Suede", Some("mbid-b")), make_node("Z", Some("mbid-z"))],
+            links: vec![GraphLink {
+                source: "Suede".to_string(),
+                target: "Z".to_string(),
+                weight: 0.9,
+            }],
+        };
+
+        let result = merge_all(vec![graph_a, graph_b]);
+
+        assert_eq!(result.nodes.len(), 3); // two distinct "Suede" + one "Z"
+        assert_eq!(result.links.len(), 2);
+        let weights: FxHashSet<String> = result
+            .links
+            .iter()
+            .map(|l| format!("{:.1}", l.weight))
+            .collect();
+        assert!(weights.contains("0.1"));
+        assert!(weights.contains("0.9"));
+    }
+}
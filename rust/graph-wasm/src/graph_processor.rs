@@ -4,7 +4,7 @@ use wasm_bindgen::prelude::*;
 
 /// Normalize string for case-insensitive comparison
 #[inline]
-fn normalize(s: &str) -> String {
+pub(crate) fn normalize(s: &str) -> String {
     s.to_lowercase()
 }
 
@@ -435,6 +435,7 @@ mod tests {
                 y: None,
                 fx: None,
                 fy: None,
+                community: None,
             },
             GraphNode {
                 id: Some("2".to_string()),
@@ -451,6 +452,7 @@ mod tests {
                 y: None,
                 fx: None,
                 fy: None,
+                community: None,
             },
         ];
 
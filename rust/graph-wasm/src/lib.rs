@@ -1,5 +1,13 @@
 use wasm_bindgen::prelude::*;
 
+mod community_detection;
+mod feature_similarity;
+mod force_layout;
+mod graph_merge;
+mod graph_processor;
+mod similarity_path;
+mod types;
+
 // When the `small` feature is enabled, use `talc` as the global allocator.
 // talc is a modern WASM allocator that replaces the deprecated wee_alloc.
 // It provides better performance (~6.7 actions/µs vs 5.9 for dlmalloc) with
@@ -0,0 +1,167 @@
+use crate::types::*;
+use rustc_hash::FxHashSet;
+use wasm_bindgen::prelude::*;
+
+/// Greedy nearest-neighbor walk over an adjacency list: from the current
+/// node, always step to the highest-weight unvisited neighbor. When the
+/// current node has no unvisited neighbors, resume from whichever unvisited
+/// node is reachable by the single largest remaining edge anywhere in the
+/// graph. If no edge can reach an unvisited node (the graph is disconnected
+/// from here on), append the remaining unvisited nodes in descending
+/// listener count.
+///
+/// Invariant: every node index `0..n` appears exactly once in the returned
+/// order. Ties are broken by lower index for determinism.
+fn greedy_walk(n: usize, adjacency: &[Vec<(u32, f32)>], listeners: &[Option<u32>], start: usize) -> Vec<u32> {
+    let mut visited = vec![false; n];
+    let mut order: Vec<u32> = Vec::with_capacity(n);
+
+    let mut current = start;
+    visited[current] = true;
+    order.push(current as u32);
+
+    while order.len() < n {
+        let next = adjacency[current]
+            .iter()
+            .filter(|(neighbor, _)| !visited[*neighbor as usize])
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then_with(|| b.0.cmp(&a.0)))
+            .map(|(neighbor, _)| *neighbor as usize);
+
+        let next = next.or_else(|| {
+            // Resume via the single largest remaining edge that reaches an
+            // unvisited node, wherever in the graph it is.
+            adjacency
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| visited[*idx])
+                .flat_map(|(_, edges)| edges.iter())
+                .filter(|(neighbor, _)| !visited[*neighbor as usize])
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then_with(|| b.0.cmp(&a.0)))
+                .map(|(neighbor, _)| *neighbor as usize)
+        });
+
+        let next = match next {
+            Some(idx) => idx,
+            None => break,
+        };
+
+        visited[next] = true;
+        order.push(next as u32);
+        current = next;
+    }
+
+    if order.len() < n {
+        // Graph is disconnected from here: append the rest by descending
+        // listener count, lower index breaking ties.
+        let mut remaining: Vec<usize> = (0..n).filter(|&i| !visited[i]).collect();
+        remaining.sort_by(|&a, &b| {
+            let listeners_a = listeners[a].unwrap_or(0);
+            let listeners_b = listeners[b].unwrap_or(0);
+            listeners_b.cmp(&listeners_a).then_with(|| a.cmp(&b))
+        });
+
+        let mut seen: FxHashSet<usize> = order.iter().map(|&i| i as usize).collect();
+        for idx in remaining {
+            if seen.insert(idx) {
+                order.push(idx as u32);
+            }
+        }
+    }
+
+    order
+}
+
+/// Build a greedy nearest-neighbor "listening journey" through a resolved
+/// similarity graph, bliss-playlist style, so the front-end can animate or
+/// export an ordered journey between artists.
+///
+/// Performance: O(N * (N + E)) worst case, since each fallback step rescans
+/// every edge.
+#[wasm_bindgen]
+pub fn build_similarity_path(graph_json: &JsValue, start_index: u32) -> Result<Vec<u32>, JsValue> {
+    let graph: ResolvedGraph = serde_wasm_bindgen::from_value(graph_json.clone())
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse graph: {}", e)))?;
+
+    let n = graph.nodes.len();
+    let start = start_index as usize;
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    if start >= n {
+        return Err(JsValue::from_str(&format!(
+            "start_index {} is out of bounds for {} nodes",
+            start_index, n
+        )));
+    }
+
+    // Adjacency list built from both directions of every link.
+    let mut adjacency: Vec<Vec<(u32, f32)>> = vec![Vec::new(); n];
+    for link in &graph.links {
+        adjacency[link.source as usize].push((link.target, link.weight));
+        adjacency[link.target as usize].push((link.source, link.weight));
+    }
+    let listeners: Vec<Option<u32>> = graph.nodes.iter().map(|node| node.listeners).collect();
+
+    Ok(greedy_walk(n, &adjacency, &listeners, start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follows_highest_weight_neighbor() {
+        // 0 -(0.9)- 1, 0 -(0.2)- 2, 1 -(0.8)- 2
+        let adjacency = vec![
+            vec![(1, 0.9), (2, 0.2)],
+            vec![(0, 0.9), (2, 0.8)],
+            vec![(0, 0.2), (1, 0.8)],
+        ];
+        let listeners = vec![None, None, None];
+
+        let order = greedy_walk(3, &adjacency, &listeners, 0);
+
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_every_node_visited_exactly_once() {
+        let adjacency = vec![
+            vec![(1, 0.5)],
+            vec![(0, 0.5), (2, 0.1)],
+            vec![(1, 0.1)],
+        ];
+        let listeners = vec![None, None, None];
+
+        let order = greedy_walk(3, &adjacency, &listeners, 0);
+
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_disconnected_graph_appends_by_listeners() {
+        // Node 0 connects only to 1; node 2 is isolated.
+        let adjacency = vec![vec![(1, 1.0)], vec![(0, 1.0)], vec![]];
+        let listeners = vec![Some(10), Some(5), Some(100)];
+
+        let order = greedy_walk(3, &adjacency, &listeners, 0);
+
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_ties_broken_by_lower_index() {
+        let adjacency = vec![
+            vec![(1, 0.5), (2, 0.5)],
+            vec![(0, 0.5)],
+            vec![(0, 0.5)],
+        ];
+        let listeners = vec![None, None, None];
+
+        let order = greedy_walk(3, &adjacency, &listeners, 0);
+
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+}
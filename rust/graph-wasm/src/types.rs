@@ -17,6 +17,14 @@ pub struct Artist {
     pub lastfm_url: Option<String>,
 }
 
+/// Per-artist numeric feature vector (tempo/energy/timbre-style descriptors)
+/// used to derive similarity edges without a pre-computed Last.fm weight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureVector {
+    pub name: String,
+    pub features: Vec<f32>,
+}
+
 /// Edge between two artists (similarity relationship)
 /// Matches the TypeScript SimilarityEdge interface
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +57,10 @@ pub struct GraphNode {
     pub y: Option<f64>,
     pub fx: Option<f64>,
     pub fy: Option<f64>,
+    /// Louvain community id assigned by `detect_communities`, used to color
+    /// nodes by cluster in the visualization.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub community: Option<u32>,
 }
 
 /// Processed graph link with string node references
@@ -100,6 +112,7 @@ impl From<Artist> for GraphNode {
             y: None,
             fx: None,
             fy: None,
+            community: None,
         }
     }
 }
@@ -162,6 +175,7 @@ mod tests {
             y: Some(200.0),
             fx: None,
             fy: None,
+            community: None,
         };
 
         let json = serde_json::to_string(&node).unwrap();